@@ -9,12 +9,19 @@
 use mysql_common::{
     constants::MAX_PAYLOAD_LEN,
     packets::{
-        column_from_payload, parse_stmt_packet, ComStmtClose, ComStmtExecuteRequestBuilder,
-        ComStmtSendLongData, StmtPacket,
+        ComStmtClose, ComStmtExecuteRequestBuilder, ComStmtSendLongData, StmtPacket,
     },
+    proto::{MyDeserialize, ParseBuf},
 };
 
-use std::{borrow::Cow, sync::Arc};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::Arc,
+};
 
 use crate::{
     conn::named_params::parse_named_params,
@@ -57,22 +64,48 @@ pub struct StmtInner {
     connection_id: u32,
 }
 
-impl StmtInner {
-    pub(crate) fn from_payload(
-        pld: &[u8],
-        connection_id: u32,
-        raw_query: Arc<str>,
-    ) -> std::io::Result<Self> {
-        let stmt_packet = parse_stmt_packet(pld)?;
+impl<'de> MyDeserialize<'de> for StmtInner {
+    const SIZE: Option<usize> = <StmtPacket as MyDeserialize>::SIZE;
+    type Ctx = u32;
+
+    /// Deserializes the fixed part of a `COM_STMT_PREPARE` response (the `StmtPacket`) through
+    /// the shared `mysql_common` `ParseBuf`/`MyDeserialize` machinery, replacing the bespoke
+    /// `parse_stmt_packet` free function this crate used to call directly.
+    ///
+    /// This does not parse the statement's column/param definitions: those arrive as their own
+    /// packets once the server knows we've seen `num_params`/`num_columns` from this packet, so
+    /// `Conn::read_column_defs` still reads and parses them separately (via the same
+    /// `ParseBuf::parse` primitive — see there) and attaches them with [`StmtInner::with_columns`]
+    /// and [`StmtInner::with_params`]. `raw_query` is likewise not on the wire; it's left empty
+    /// here and filled in by [`StmtInner::from_payload`] right after.
+    ///
+    /// Note: `mysql_common::packets::Column` is an owned type with no lifetime parameter, so
+    /// this refactor unifies the parsing entry point but does not make column definitions
+    /// zero-copy/borrowed — they're still promoted straight to owned storage.
+    fn deserialize(connection_id: Self::Ctx, buf: &mut ParseBuf<'de>) -> std::io::Result<Self> {
+        let stmt_packet = buf.parse(())?;
 
         Ok(Self {
-            raw_query,
+            raw_query: Arc::from(""),
             columns: None,
             params: None,
             stmt_packet,
             connection_id,
         })
     }
+}
+
+impl StmtInner {
+    pub(crate) fn from_payload(
+        pld: &[u8],
+        connection_id: u32,
+        raw_query: Arc<str>,
+    ) -> std::io::Result<Self> {
+        let mut buf = ParseBuf(pld);
+        let mut stmt_inner = Self::deserialize(connection_id, &mut buf)?;
+        stmt_inner.raw_query = raw_query;
+        Ok(stmt_inner)
+    }
 
     pub(crate) fn with_params(mut self, params: Vec<Column>) -> Self {
         self.params = if params.is_empty() {
@@ -157,7 +190,230 @@ impl Statement {
     }
 }
 
+/// A blob/text parameter streamed from an [`AsyncRead`] instead of being buffered into a
+/// [`Value::Bytes`].
+///
+/// Bind it by index via [`Conn::execute_statement_with_streams`] so large `LONGBLOB`/`LONGTEXT`
+/// values are sent as a sequence of `ComStmtSendLongData` packets without first being collected
+/// into a single `Value::Bytes`. The reader is still read to EOF and all of its chunks are held
+/// in memory before the first packet is sent, so memory use grows with the size of the stream.
+pub struct StreamParam {
+    reader: Pin<Box<dyn AsyncRead + Send>>,
+    len: Option<u64>,
+}
+
+impl std::fmt::Debug for StreamParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamParam").field("len", &self.len).finish_non_exhaustive()
+    }
+}
+
+impl StreamParam {
+    /// Wraps `reader` as a streamed parameter.
+    ///
+    /// `len`, if known, is purely informational today — the payload is still read to EOF and
+    /// sent as however many `ComStmtSendLongData` chunks that takes.
+    pub fn new<R>(reader: R, len: Option<u64>) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self {
+            reader: Box::pin(reader),
+            len,
+        }
+    }
+}
+
+/// Snapshot of a connection's prepared-statement cache usage.
+///
+/// Returned by [`crate::Conn::stmt_cache_stats`]. Counters accumulate for the lifetime of the
+/// connection and are not reset when the cache is resized.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct StmtCacheStats {
+    /// Number of times a cached statement was reused instead of being re-prepared.
+    pub hits: usize,
+    /// Number of times a statement had to be prepared because it wasn't in the cache.
+    pub misses: usize,
+    /// Number of statements evicted from the cache, either to make room for a new one or
+    /// because the cache was shrunk.
+    pub evictions: usize,
+    /// Number of statements currently held in the cache.
+    pub len: usize,
+}
+
+/// Per-connection LRU cache of prepared statements, keyed by raw query text.
+///
+/// Inserting past `capacity` evicts the least-recently-used entry; the caller is responsible
+/// for sending `COM_STMT_CLOSE` for whatever is returned from [`StmtCache::insert`], the same
+/// way [`Conn::prepare_statement`](crate::Conn) already closes a displaced statement today.
+#[derive(Debug)]
+pub(crate) struct StmtCache {
+    capacity: usize,
+    cache: HashMap<Arc<str>, Arc<StmtInner>>,
+    lru_order: VecDeque<Arc<str>>,
+    stats: StmtCacheStats,
+}
+
+impl StmtCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            cache: HashMap::new(),
+            lru_order: VecDeque::new(),
+            stats: StmtCacheStats::default(),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> StmtCacheStats {
+        StmtCacheStats {
+            len: self.cache.len(),
+            ..self.stats
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the cache's capacity, evicting least-recently-used entries until the cache
+    /// fits if it's shrinking. Returns the evicted statements so the caller can close them.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) -> Vec<Arc<StmtInner>> {
+        self.capacity = capacity;
+
+        let mut evicted = Vec::new();
+        while self.cache.len() > self.capacity {
+            let Some(evicted_query) = self.lru_order.pop_front() else {
+                break;
+            };
+            if let Some(stmt_inner) = self.cache.remove(&evicted_query) {
+                self.stats.evictions += 1;
+                evicted.push(stmt_inner);
+            }
+        }
+
+        evicted
+    }
+
+    /// Looks up `raw_query`, marking it as most-recently-used on a hit.
+    pub(crate) fn get(&mut self, raw_query: &str) -> Option<Arc<StmtInner>> {
+        let stmt_inner = self.cache.get(raw_query).cloned();
+
+        if let Some(stmt_inner) = &stmt_inner {
+            self.stats.hits += 1;
+            self.touch(&stmt_inner.raw_query);
+        } else {
+            self.stats.misses += 1;
+        }
+
+        stmt_inner
+    }
+
+    /// Inserts a freshly prepared statement, evicting the least-recently-used entry if the
+    /// cache is over capacity. Returns the evicted statement, or the statement previously cached
+    /// under the same query if there was one, so the caller can close it on the server.
+    pub(crate) fn insert(&mut self, stmt_inner: Arc<StmtInner>) -> Option<Arc<StmtInner>> {
+        if self.capacity == 0 {
+            // Caching is disabled: `stmt_inner` was never stored, so there's nothing to evict.
+            // Returning it here would make the caller close the handle it's about to execute.
+            return None;
+        }
+
+        let raw_query = Arc::clone(&stmt_inner.raw_query);
+        let displaced = self.cache.insert(Arc::clone(&raw_query), stmt_inner);
+        if displaced.is_none() {
+            self.lru_order.push_back(raw_query);
+        } else {
+            self.touch(&raw_query);
+        }
+
+        if self.cache.len() > self.capacity {
+            let evicted_query = self.lru_order.pop_front();
+            let evicted = evicted_query.and_then(|query| self.cache.remove(&query));
+            if evicted.is_some() {
+                self.stats.evictions += 1;
+            }
+            evicted
+        } else {
+            displaced
+        }
+    }
+
+    /// Moves `raw_query` to the back of the LRU order (i.e. marks it most-recently-used).
+    fn touch(&mut self, raw_query: &Arc<str>) {
+        if let Some(pos) = self.lru_order.iter().position(|q| q == raw_query) {
+            let query = self.lru_order.remove(pos).unwrap();
+            self.lru_order.push_back(query);
+        }
+    }
+}
+
+/// Reads `reader` to EOF, returning the sequence of payloads to send as `ComStmtSendLongData`
+/// chunks for it: pieces of at most `MAX_PAYLOAD_LEN - 6` bytes, or a single empty chunk if the
+/// stream yielded no data at all, matching the `bytes.is_empty()` edge case of the buffered
+/// `Value::Bytes` path above. Kept free of any `Conn`/network access so it's unit-testable on
+/// its own.
+async fn read_stream_chunks(
+    reader: &mut Pin<Box<dyn AsyncRead + Send>>,
+) -> std::io::Result<Vec<Vec<u8>>> {
+    let mut chunks = Vec::new();
+    let mut buf = vec![0u8; MAX_PAYLOAD_LEN - 6];
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            if chunks.is_empty() {
+                chunks.push(Vec::new());
+            }
+            break;
+        }
+        chunks.push(buf[..n].to_vec());
+    }
+
+    Ok(chunks)
+}
+
+/// The cache-hit half of [`Conn::reprepare_statement`]'s selection logic: looks `statement`'s
+/// raw query up in `cache` and, if present, returns the `Statement` `reprepare_statement` should
+/// return for it (same cached `StmtInner`, with `statement`'s own named params carried over).
+/// Returns `None` on a cache miss, leaving the (network-bound) prepare to the caller. Kept free
+/// of any `Conn`/network access so the hit path and named-param propagation are unit-testable on
+/// their own, the way [`read_stream_chunks`] is for the streaming path.
+fn pick_cached_statement(cache: &mut StmtCache, statement: &Statement) -> Option<Statement> {
+    let stmt_inner = cache.get(statement.inner.raw_query.as_ref())?;
+    Some(Statement::new(stmt_inner, statement.named_params.clone()))
+}
+
 impl crate::Conn {
+    /// Returns hit/miss/eviction counters and the current size of this connection's prepared
+    /// statement cache.
+    pub fn stmt_cache_stats(&self) -> StmtCacheStats {
+        self.conn_ref().stmt_cache().stats()
+    }
+
+    /// Returns the maximum number of prepared statements this connection's cache will hold.
+    pub fn stmt_cache_size(&self) -> usize {
+        self.conn_ref().stmt_cache().capacity()
+    }
+
+    /// Sets the maximum number of prepared statements this connection's cache will hold,
+    /// closing any statements evicted as a result of shrinking it. A capacity of `0` disables
+    /// caching entirely.
+    ///
+    /// This is a post-connect, runtime-only knob: it resizes the cache of an already-established
+    /// `Conn`, it does not configure the cache size a new connection is opened with. Wiring a
+    /// cache size into connection setup (the way e.g. `stmt_cache_size` is configured on the
+    /// connection options of other mysql_async forks) would live on the options/builder type
+    /// that connections are constructed from, which this module doesn't own and isn't part of
+    /// this change — callers who need a non-default size from the moment a connection opens
+    /// should call this right after connecting, before preparing any statements.
+    pub async fn set_stmt_cache_size(&mut self, capacity: usize) -> Result<()> {
+        let evicted = self.conn_mut().stmt_cache_mut().set_capacity(capacity);
+        for stmt_inner in evicted {
+            self.close_statement(stmt_inner.id()).await?;
+        }
+        Ok(())
+    }
+
     /// Low-level helpers, that reads the given number of column packets from server.
     ///
     /// Requires `num > 0`.
@@ -168,9 +424,12 @@ impl crate::Conn {
         let num = num.into();
         debug_assert!(num > 0);
         let packets = self.read_packets(num).await?;
+        // Parsed through the same `ParseBuf::parse` primitive as `StmtInner`'s `MyDeserialize`
+        // impl, replacing the old `column_from_payload` free function. `Column` has no lifetime
+        // parameter, so each entry is still fully owned once parsed, not a borrowed view.
         let defs = packets
-            .into_iter()
-            .map(column_from_payload)
+            .iter()
+            .map(|packet| ParseBuf(packet).parse::<Column>(()))
             .collect::<std::result::Result<Vec<Column>, _>>()
             .map_err(Error::from)?;
 
@@ -186,12 +445,19 @@ impl crate::Conn {
     }
 
     /// Helper, that retrieves `Statement` from `StatementLike`.
+    ///
+    /// Note that `StatementLike::info` only yields the raw query text and named params, so
+    /// the statement this returns is always looked up/prepared against `self` and is never
+    /// bound to another connection's id, even if `stmt_like` is a `Statement` obtained from
+    /// a different pooled connection.
     pub(crate) async fn get_statement<U>(&mut self, stmt_like: &U) -> Result<Statement>
     where
         U: StatementLike + ?Sized,
     {
         let (named_params, raw_query) = stmt_like.info()?;
-        let stmt_inner = if let Some(stmt_inner) = self.get_cached_stmt(raw_query.as_ref()) {
+        let stmt_inner = if let Some(stmt_inner) =
+            self.conn_mut().stmt_cache_mut().get(raw_query.as_ref())
+        {
             stmt_inner
         } else {
             self.prepare_statement(raw_query).await?
@@ -223,13 +489,38 @@ impl crate::Conn {
 
         let inner_stmt = Arc::new(inner_stmt);
 
-        if let Some(old_stmt) = self.conn_mut().cache_stmt(&inner_stmt) {
+        if let Some(old_stmt) = self
+            .conn_mut()
+            .stmt_cache_mut()
+            .insert(Arc::clone(&inner_stmt))
+        {
             self.close_statement(old_stmt.id()).await?;
         }
 
         Ok(inner_stmt)
     }
 
+    /// Re-prepares the given statement on this connection and returns a fresh `Statement`
+    /// bound to it.
+    ///
+    /// `Statement`s carry the id of the connection they were prepared on, so a `Statement`
+    /// obtained from one pooled connection is not valid on another. Reusing the cached
+    /// statement (or preparing a new one) for the same `raw_query` keeps this transparent
+    /// to the caller.
+    async fn reprepare_statement(&mut self, statement: &Statement) -> Result<Statement> {
+        if let Some(reprepared) =
+            pick_cached_statement(self.conn_mut().stmt_cache_mut(), statement)
+        {
+            return Ok(reprepared);
+        }
+
+        let raw_query = Arc::clone(&statement.inner.raw_query);
+        let stmt_inner = self
+            .prepare_statement(Cow::Borrowed(raw_query.as_ref()))
+            .await?;
+        Ok(Statement::new(stmt_inner, statement.named_params.clone()))
+    }
+
     /// Helper, that executes the given statement with the given params.
     pub(crate) async fn execute_statement<P>(
         &mut self,
@@ -239,6 +530,45 @@ impl crate::Conn {
     where
         P: Into<Params>,
     {
+        self.execute_statement_with_streams(statement, params, Vec::new())
+            .await
+    }
+
+    /// Like [`Conn::execute_statement`], but additionally streams the given `(param_index,
+    /// StreamParam)` pairs as long data, reading each one to EOF instead of requiring its value
+    /// to already be present in `params` as a `Value::Bytes`.
+    ///
+    /// The positional index in each pair refers to the same zero-based parameter ordering as
+    /// `params`; a streamed index must still have a `Value::Bytes` placeholder at that position
+    /// so parameter count/type validation sees the right number of params and the wire type tag
+    /// matches the blob/text column. The placeholder's *content* doesn't matter and is
+    /// overwritten internally (see below) — it exists only to reserve the slot.
+    ///
+    /// `ComStmtExecuteRequestBuilder` decides, from the params it's given, whether the whole
+    /// request needs long data (i.e. whether any `Value::Bytes` is big enough that inlining it
+    /// would blow the packet size budget) — that decision is global to the request, not
+    /// per-parameter, and baked into the returned body. A small/empty placeholder would make it
+    /// decide `false`, and then the placeholder is serialized inline *after* we've already sent
+    /// the real payload via `ComStmtSendLongData`, corrupting the parameter. So whenever
+    /// `streams` is non-empty, every streamed index is forced to an oversized placeholder before
+    /// building the request, guaranteeing the builder picks long data for the whole call.
+    pub(crate) async fn execute_statement_with_streams<P>(
+        &mut self,
+        statement: &Statement,
+        params: P,
+        mut streams: Vec<(usize, StreamParam)>,
+    ) -> Result<QueryResult<'_, Self, BinaryProtocol>>
+    where
+        P: Into<Params>,
+    {
+        let local_statement;
+        let statement = if statement.connection_id() != self.conn_ref().id() {
+            local_statement = self.reprepare_statement(statement).await?;
+            &local_statement
+        } else {
+            statement
+        };
+
         let mut params = params.into();
         loop {
             match params {
@@ -250,13 +580,35 @@ impl crate::Conn {
                         })?
                     }
 
-                    let params = params.into_iter().collect::<Vec<_>>();
+                    let mut params = params.into_iter().collect::<Vec<_>>();
+
+                    for &(index, _) in &streams {
+                        let Some(value) = params.get_mut(index) else {
+                            return Err(DriverError::StreamParamIndexOutOfBounds {
+                                index,
+                                num_params: statement.num_params(),
+                            }
+                            .into());
+                        };
+                        // Oversized on purpose: forces the builder below to treat this request
+                        // as needing long data, regardless of where its own size threshold is.
+                        // Real content is irrelevant — this value is never sent inline once long
+                        // data is in effect, only the actual stream is (via send_long_data).
+                        *value = Value::Bytes(vec![0u8; MAX_PAYLOAD_LEN]);
+                    }
 
                     let (body, as_long_data) =
-                        ComStmtExecuteRequestBuilder::new(statement.id()).build(&*params);
+                        ComStmtExecuteRequestBuilder::new(statement.id()).build(&params);
+
+                    if !streams.is_empty() && !as_long_data {
+                        // Should be unreachable given the forced placeholder above; bail out
+                        // loudly rather than silently sending the streamed value inline/corrupt.
+                        return Err(DriverError::StreamParamRequiresLongData.into());
+                    }
 
                     if as_long_data {
-                        self.send_long_data(statement.id(), params.iter()).await?;
+                        self.send_long_data(statement.id(), params.iter(), streams)
+                            .await?;
                     }
 
                     self.write_command_raw(body).await?;
@@ -294,12 +646,46 @@ impl crate::Conn {
         }
     }
 
-    /// Helper, that sends all `Value::Bytes` in the given list of paramenters as long data.
-    async fn send_long_data<'a, I>(&mut self, statement_id: u32, params: I) -> Result<()>
+    /// Prepares (or reuses a cached preparation of) `stmt_like`, then executes it with `params`,
+    /// streaming the given `(param_index, StreamParam)` pairs as long data instead of requiring
+    /// their value to already be buffered in `params`.
+    ///
+    /// This is the public counterpart of [`Conn::execute_statement_with_streams`] — it exists so
+    /// that callers outside this crate can actually reach the streaming path; combine it with
+    /// [`Conn::get_statement`] semantics by passing the same `stmt_like` you'd pass to `exec`.
+    pub async fn exec_stmt_with_streams<U, P>(
+        &mut self,
+        stmt_like: &U,
+        params: P,
+        streams: Vec<(usize, StreamParam)>,
+    ) -> Result<QueryResult<'_, Self, BinaryProtocol>>
+    where
+        U: StatementLike + ?Sized,
+        P: Into<Params>,
+    {
+        let statement = self.get_statement(stmt_like).await?;
+        self.execute_statement_with_streams(&statement, params, streams)
+            .await
+    }
+
+    /// Helper, that sends all `Value::Bytes` in the given list of parameters, plus any
+    /// [`StreamParam`]s bound by index, as long data.
+    async fn send_long_data<'a, I>(
+        &mut self,
+        statement_id: u32,
+        params: I,
+        streams: Vec<(usize, StreamParam)>,
+    ) -> Result<()>
     where
         I: Iterator<Item = &'a Value>,
     {
+        let stream_indexes = streams.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
         for (i, value) in params.enumerate() {
+            if stream_indexes.contains(&i) {
+                continue;
+            }
+
             if let Value::Bytes(bytes) = value {
                 let chunks = bytes.chunks(MAX_PAYLOAD_LEN - 6);
                 let chunks = chunks.chain(if bytes.is_empty() {
@@ -314,6 +700,13 @@ impl crate::Conn {
             }
         }
 
+        for (i, mut stream) in streams {
+            for chunk in read_stream_chunks(&mut stream.reader).await? {
+                let com = ComStmtSendLongData::new(statement_id, i, &chunk[..]);
+                self.write_command_raw(com.into()).await?;
+            }
+        }
+
         Ok(())
     }
 
@@ -322,3 +715,172 @@ impl crate::Conn {
         self.write_command_raw(ComStmtClose::new(id).into()).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxed_reader(data: Vec<u8>) -> Pin<Box<dyn AsyncRead + Send>> {
+        Box::pin(std::io::Cursor::new(data))
+    }
+
+    /// Builds a minimal, well-formed `COM_STMT_PREPARE` OK-packet payload (status byte,
+    /// statement id, zero columns/params, no warnings) so `StmtInner::from_payload` has
+    /// something real to parse in tests.
+    fn stmt_prepare_payload(statement_id: u32) -> Vec<u8> {
+        let mut payload = vec![0u8; 12];
+        payload[1..5].copy_from_slice(&statement_id.to_le_bytes());
+        payload
+    }
+
+    fn stmt_inner(statement_id: u32, raw_query: &str) -> Arc<StmtInner> {
+        Arc::new(
+            StmtInner::from_payload(&stmt_prepare_payload(statement_id), 1, raw_query.into())
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn stmt_cache_insert_with_zero_capacity_does_not_cache_or_evict() {
+        let mut cache = StmtCache::new(0);
+        let stmt = stmt_inner(1, "SELECT 1");
+
+        let displaced = cache.insert(Arc::clone(&stmt));
+
+        assert!(displaced.is_none());
+        assert_eq!(cache.stats().len, 0);
+        assert_eq!(cache.stats().evictions, 0);
+        assert!(cache.get("SELECT 1").is_none());
+    }
+
+    #[test]
+    fn stmt_cache_evicts_least_recently_used_entry_past_capacity() {
+        let mut cache = StmtCache::new(2);
+
+        assert!(cache.insert(stmt_inner(1, "SELECT 1")).is_none());
+        assert!(cache.insert(stmt_inner(2, "SELECT 2")).is_none());
+
+        // Touch "SELECT 1" so "SELECT 2" becomes the least-recently-used entry.
+        assert!(cache.get("SELECT 1").is_some());
+
+        let evicted = cache.insert(stmt_inner(3, "SELECT 3"));
+
+        assert_eq!(evicted.map(|s| s.id()), Some(2));
+        assert!(cache.get("SELECT 2").is_none());
+        assert!(cache.get("SELECT 1").is_some());
+        assert!(cache.get("SELECT 3").is_some());
+    }
+
+    #[test]
+    fn stmt_cache_tracks_hit_miss_and_eviction_counts() {
+        let mut cache = StmtCache::new(1);
+
+        cache.insert(stmt_inner(1, "SELECT 1"));
+        assert!(cache.get("SELECT 1").is_some()); // hit
+        assert!(cache.get("SELECT 2").is_none()); // miss
+        cache.insert(stmt_inner(2, "SELECT 2")); // evicts "SELECT 1"
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.len, 1);
+    }
+
+    #[test]
+    fn stmt_cache_set_capacity_shrinks_and_reports_evicted_entries() {
+        let mut cache = StmtCache::new(3);
+        cache.insert(stmt_inner(1, "SELECT 1"));
+        cache.insert(stmt_inner(2, "SELECT 2"));
+        cache.insert(stmt_inner(3, "SELECT 3"));
+
+        let evicted = cache.set_capacity(1);
+
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(evicted.len(), 2);
+        assert_eq!(cache.stats().len, 1);
+        assert!(cache.get("SELECT 3").is_some());
+    }
+
+    #[tokio::test]
+    async fn read_stream_chunks_sends_one_empty_chunk_for_an_empty_stream() {
+        let mut reader = boxed_reader(Vec::new());
+        let chunks = read_stream_chunks(&mut reader).await.unwrap();
+        assert_eq!(chunks, vec![Vec::<u8>::new()]);
+    }
+
+    #[tokio::test]
+    async fn read_stream_chunks_splits_large_payloads_at_max_payload_len() {
+        let data = vec![0xAB; (MAX_PAYLOAD_LEN - 6) + 1];
+        let mut reader = boxed_reader(data.clone());
+        let chunks = read_stream_chunks(&mut reader).await.unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), MAX_PAYLOAD_LEN - 6);
+        assert_eq!(chunks[1].len(), 1);
+        assert_eq!(chunks.concat(), data);
+    }
+
+    #[tokio::test]
+    async fn read_stream_chunks_returns_small_payload_as_a_single_chunk() {
+        let mut reader = boxed_reader(b"hello".to_vec());
+        let chunks = read_stream_chunks(&mut reader).await.unwrap();
+        assert_eq!(chunks, vec![b"hello".to_vec()]);
+    }
+
+    /// `Conn::execute_statement_with_streams` decides whether to re-prepare a `Statement` by
+    /// comparing `Statement::connection_id()` against the current connection's id. There's no
+    /// fake-server/`Conn` harness in this crate to drive that method end-to-end in a unit test,
+    /// so this instead locks down the data the guard actually reads: a `Statement`'s
+    /// `connection_id` always reflects the connection it was prepared on, distinct statements
+    /// prepared on different connections compare unequal, and the same connection id compares
+    /// equal regardless of which statement it's attached to.
+    #[test]
+    fn statement_connection_id_reflects_the_connection_it_was_prepared_on() {
+        let inner_a =
+            StmtInner::from_payload(&stmt_prepare_payload(1), 10, "SELECT 1".into()).unwrap();
+        let inner_b =
+            StmtInner::from_payload(&stmt_prepare_payload(2), 20, "SELECT 2".into()).unwrap();
+        let inner_c =
+            StmtInner::from_payload(&stmt_prepare_payload(3), 10, "SELECT 3".into()).unwrap();
+
+        let stmt_a = Statement::new(Arc::new(inner_a), None);
+        let stmt_b = Statement::new(Arc::new(inner_b), None);
+        let stmt_c = Statement::new(Arc::new(inner_c), None);
+
+        assert_eq!(stmt_a.connection_id(), 10);
+        assert_ne!(stmt_a.connection_id(), stmt_b.connection_id());
+        assert_eq!(stmt_a.connection_id(), stmt_c.connection_id());
+    }
+
+    #[test]
+    fn pick_cached_statement_returns_the_cached_entry_on_a_hit() {
+        let mut cache = StmtCache::new(1);
+        cache.insert(stmt_inner(7, "SELECT 1"));
+
+        // The statement passed in is stale (wrong id/connection) — only its raw query and
+        // named params should matter; the cached StmtInner should win.
+        let stale = Statement::new(
+            stmt_inner(999, "SELECT 1"),
+            Some(vec!["a".to_string(), "b".to_string()]),
+        );
+
+        let picked = pick_cached_statement(&mut cache, &stale).expect("cache hit");
+
+        assert_eq!(picked.id(), 7);
+        assert_eq!(
+            picked.named_params,
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn pick_cached_statement_returns_none_on_a_miss() {
+        let mut cache = StmtCache::new(1);
+        let statement = Statement::new(stmt_inner(1, "SELECT 1"), None);
+
+        assert!(pick_cached_statement(&mut cache, &statement).is_none());
+        assert_eq!(cache.stats().misses, 1);
+    }
+}